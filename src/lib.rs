@@ -41,27 +41,44 @@
 //! }
 //!
 //! let pool: Pool<Obj> = Pool::default();
-//! let obj = pool.pull("item1", || Obj{size: 1});
+//! let obj = pool.pull(&"item1".to_string(), || Obj{size: 1});
 //! assert_eq!(obj.size, 1);
-//! let obj2 = pool.pull("item1", || Obj{size: 1});
+//! let obj2 = pool.pull(&"item1".to_string(), || Obj{size: 1});
 //! assert_eq!(obj.size, 1);
 //! ```
 //! Pull from pool and `detach()`
 //! ```
 //! use index_pool::Pool;
 //!
+//! #[derive(Debug)]
 //! struct Obj {
 //!     size: usize,
 //! }
 //!
 //! let pool: Pool<Obj> = Pool::default();
-//! let obj = pool.pull("item1", || Obj{size: 1});
+//! let obj = pool.pull(&"item1".to_string(), || Obj{size: 1});
 //! assert_eq!(obj.size, 1);
-//! let obj2 = pool.pull("item1", || Obj{size: 1});
+//! let obj2 = pool.pull(&"item1".to_string(), || Obj{size: 1});
 //! assert_eq!(obj.size, 1);
 //! let (pool, obj) = obj.detach();
 //! assert_eq!(obj.size, 1);
-//! pool.attach("item1", obj);
+//! pool.reattach(obj).unwrap();
+//! ```
+//!
+//! ## Indexing by a Custom Key
+//!
+//! `Pool<T, K>` is generic over its index key `K: Ord + Clone + Debug`, which defaults to
+//! `String` for source compatibility. Keying directly on a type like [`std::net::SocketAddr`]
+//! avoids stringifying an address on every pull, which matters for a hot-path SSH or DB
+//! connection pool:
+//! ```
+//! use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+//! use index_pool::Pool;
+//!
+//! let pool: Pool<String, SocketAddr> = Pool::default();
+//! let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 22);
+//! let conn = pool.pull(&addr, || String::from("ssh-connection"));
+//! assert_eq!(*conn, "ssh-connection");
 //! ```
 //!
 //! ## Using Across Threads
@@ -73,20 +90,140 @@
 //!
 //! let pool: Arc<Pool<String>> = Arc::new(Pool::default());
 //! ```
+//! A plain [`Reusable`] borrows the pool, so it can't outlive the stack frame that pulled it.
+//! When a lease needs to move into a spawned thread or task, pull an [`OwnedReusable`] instead,
+//! which holds its own `Arc<Pool<T>>`:
+//! ```no_run
+//! use std::sync::Arc;
+//! use index_pool::Pool;
+//!
+//! let pool: Arc<Pool<String>> = Arc::new(Pool::default());
+//! let obj = pool.pull_owned(&"item1".to_string(), || String::from("hello"));
+//! std::thread::spawn(move || {
+//!     assert_eq!(*obj, "hello");
+//! });
+//! ```
+//!
+//! ## Resetting Objects on Return
+//!
+//! By default objects are returned to the pool as-is, NOT reset. Implement [`Recycle`] for your
+//! type and create the pool with [`Pool::new_recycling`] to have it cleaned up automatically, or
+//! use [`Pool::new_with_recycler`] with any closure for types you don't own (e.g. `Vec::clear`
+//! for `Vec<u8>`):
+//! ```
+//! use std::time::Duration;
+//! use index_pool::{Pool, Recycle};
+//!
+//! struct Buffer {
+//!     data: Vec<u8>,
+//! }
+//!
+//! impl Recycle for Buffer {
+//!     fn reset(&mut self) {
+//!         self.data.clear();
+//!     }
+//! }
+//!
+//! let pool: Pool<Buffer> = Pool::new_recycling(32, 8, Duration::from_secs(300));
+//! ```
 //!
-//! # Warning
+//! ## Checking Liveness on Checkout
 //!
-//! Objects in the pool are not automatically reset, they are returned but NOT reset
-//! You may want to call `object.reset()` or  `object.clear()`
-//! or any other equivalent for the object that you are using, after pulling from the pool
+//! Expiration alone doesn't catch a pooled connection that died for other reasons (the peer
+//! closed the socket, say). Implement [`Checkout`] and create the pool with
+//! [`Pool::new_checking`] to have `try_pull` skip dead candidates instead of returning them, or
+//! use [`Pool::new_checked`] with any closure for types you don't own:
+//! ```
+//! use std::time::Duration;
+//! use index_pool::{Checkout, Pool};
+//!
+//! struct Connection {
+//!     alive: bool,
+//! }
+//!
+//! impl Checkout for Connection {
+//!     fn is_usable(&self) -> bool {
+//!         self.alive
+//!     }
+//! }
+//!
+//! let pool: Pool<Connection> = Pool::new_checking(32, 8, Duration::from_secs(300));
+//! ```
+//!
+//! ## Backpressure with the `async` Feature
+//!
+//! [`Pool::pull`] reacts to an empty index by eagerly manufacturing a whole batch of fresh
+//! objects, which gives an expensive resource no real cap. With the `async` feature enabled,
+//! [`Pool::pull_async`] enforces `max_single_pool_size` as a true limit: it creates at most one
+//! new object per call and awaits a free slot once the index is at capacity.
+//! ```
+//! use std::time::Duration;
+//! use index_pool::Pool;
+//!
+//! #[cfg(feature = "async")]
+//! fn example() {
+//!     let pool: Pool<String> = Pool::default();
+//!     tokio::runtime::Runtime::new().unwrap().block_on(async {
+//!         let obj = pool.pull_async(&"item1".to_string(), || String::from("hello")).await;
+//!         assert_eq!(*obj, "hello");
+//!     });
+//! }
+//!
+//! #[cfg(not(feature = "async"))]
+//! fn example() {}
+//!
+//! example();
+//! ```
+//!
+//! Expiration is otherwise only enforced lazily inside `try_pull`, so a pool that sees a burst
+//! of traffic and then goes quiet keeps dead objects pooled indefinitely. [`Pool::spawn_reaper`]
+//! spawns a background task that periodically sweeps every index for expired objects:
+//! ```
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use index_pool::Pool;
+//!
+//! #[cfg(feature = "async")]
+//! fn example() {
+//!     let pool: Arc<Pool<String>> = Arc::new(Pool::default());
+//!     tokio::runtime::Runtime::new().unwrap().block_on(async {
+//!         let reaper = pool.spawn_reaper(Duration::from_secs(30));
+//!         // `reaper` stops the background sweep when dropped.
+//!         drop(reaper);
+//!     });
+//! }
+//!
+//! #[cfg(not(feature = "async"))]
+//! fn example() {}
+//!
+//! example();
+//! ```
+//!
+//! ## Safely Re-attaching Detached Objects
+//!
+//! [`Reusable::detach`] hands back a [`DetachedHandle`] instead of a bare value. It remembers
+//! which generation its index was on, so [`Pool::reattach`] can tell whether that index has since
+//! been emptied out by the reaper or expunged under memory pressure, and refuses to resurrect a
+//! stale handle, returning `Err` with the value instead:
+//! ```
+//! use index_pool::Pool;
+//!
+//! let pool: Pool<String> = Pool::default();
+//! let (pool, handle) = pool.pull(&"item1".to_string(), || String::from("hello")).detach();
+//! assert!(pool.reattach(handle).is_ok());
+//! ```
 //!
 //! [`std::sync::Arc`]: https://doc.rust-lang.org/stable/std/sync/struct.Arc.html
 extern crate log;
 extern crate parking_lot;
 
+use std::borrow::Borrow;
 use std::collections::BTreeMap;
+use std::fmt;
 use std::mem::{forget, ManuallyDrop};
 use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use parking_lot::RwLock;
@@ -101,6 +238,29 @@ const DEFAULT_POOL_INDEXES: usize = 32;
 const DEFAULT_SINGLE_POOL_SIZE: usize = 8;
 const DEFAULT_EXPIRATION: Duration = Duration::from_secs(300);
 
+/// Resets a pooled object to a clean state before it is reused.
+///
+/// Implement this for objects that accumulate state between uses (buffers, connections, ...),
+/// then create the pool with [`Pool::new_recycling`] to have `reset` called automatically on
+/// every return. The default implementation is a no-op, so types that don't need resetting can
+/// `impl Recycle for MyType {}` and move on.
+pub trait Recycle {
+    fn reset(&mut self) {}
+}
+
+/// Validates that a pooled object is still usable before it is handed back out.
+///
+/// Implement this for resources that can die out from under the pool (sockets, connections,
+/// ...), then create the pool with [`Pool::new_checking`] to have `is_usable` consulted
+/// automatically. `try_pull` discards candidates that fail the check and keeps looking rather
+/// than handing a dead object back to the caller. The default implementation always reports the
+/// object as usable.
+pub trait Checkout {
+    fn is_usable(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Debug)]
 struct Inner<T> {
     inner: T,
@@ -117,20 +277,131 @@ impl<T> Inner<T> {
     }
 }
 
-#[derive(Debug)]
-pub struct Pool<T> {
+/// Resets a pooled object to a clean state before it is returned; see [`Pool::new_with_recycler`].
+type Recycler<T> = Arc<dyn Fn(&mut T) + Send + Sync>;
+
+/// Validates that a pooled object is still usable before it is handed back out; see [`Pool::new_checked`].
+type Checker<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+pub struct Pool<T, K = String>
+where
+    K: Ord + Clone + fmt::Debug,
+{
     max_pool_indexes: usize,
     max_single_pool_size: usize,
     expiration: Duration,
-    objects: RwLock<BTreeMap<String, Vec<Inner<T>>>>,
+    objects: RwLock<BTreeMap<K, Vec<Inner<T>>>>,
+    recycler: Option<Recycler<T>>,
+    checker: Option<Checker<T>>,
+    /// Per-index generation counters, bumped whenever an index is created or fully expunged, so
+    /// a [`DetachedHandle`] pulled under a since-evicted generation can be told apart from one
+    /// still valid for [`Pool::reattach`].
+    ///
+    /// Entries are intentionally never removed, even once their index is gone: dropping a
+    /// counter would let a key that's reused later start back at generation 0, reopening the
+    /// exact ABA hazard generations exist to close. This does mean the map grows with the number
+    /// of distinct keys ever seen, unlike `objects` and (for the `async` feature) `semaphores`.
+    generations: RwLock<BTreeMap<K, Arc<AtomicU64>>>,
+    #[cfg(feature = "async")]
+    semaphores: RwLock<BTreeMap<K, Arc<tokio::sync::Semaphore>>>,
+}
+
+impl<T, K> fmt::Debug for Pool<T, K>
+where
+    T: fmt::Debug,
+    K: Ord + Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("max_pool_indexes", &self.max_pool_indexes)
+            .field("max_single_pool_size", &self.max_single_pool_size)
+            .field("expiration", &self.expiration)
+            .field("objects", &self.objects)
+            .field("recycler", &self.recycler.as_ref().map(|_| "Fn(&mut T)"))
+            .field("checker", &self.checker.as_ref().map(|_| "Fn(&T) -> bool"))
+            .finish()
+    }
 }
 
-impl<T> Pool<T> {
-    pub fn new(max_pool_indexes: usize, max_single_pool_size: usize, expiration: Duration) -> Pool<T> {
-        Pool { max_pool_indexes, max_single_pool_size, expiration, objects: RwLock::new(BTreeMap::new()) }
+impl<T, K> Pool<T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    pub fn new(max_pool_indexes: usize, max_single_pool_size: usize, expiration: Duration) -> Pool<T, K> {
+        Pool {
+            max_pool_indexes,
+            max_single_pool_size,
+            expiration,
+            objects: RwLock::new(BTreeMap::new()),
+            recycler: None,
+            checker: None,
+            generations: RwLock::new(BTreeMap::new()),
+            #[cfg(feature = "async")]
+            semaphores: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Creates a pool that resets every object with `reset_fn` right before it is returned.
+    ///
+    /// Use this when you can't implement [`Recycle`] on `T` yourself, e.g. for foreign types
+    /// like `Vec<u8>` where `reset_fn` would just be `Vec::clear`.
+    pub fn new_with_recycler<F>(max_pool_indexes: usize, max_single_pool_size: usize, expiration: Duration, reset_fn: F) -> Pool<T, K>
+    where
+        F: Fn(&mut T) + Send + Sync + 'static,
+    {
+        Pool {
+            max_pool_indexes,
+            max_single_pool_size,
+            expiration,
+            objects: RwLock::new(BTreeMap::new()),
+            recycler: Some(Arc::new(reset_fn)),
+            checker: None,
+            generations: RwLock::new(BTreeMap::new()),
+            #[cfg(feature = "async")]
+            semaphores: RwLock::new(BTreeMap::new()),
+        }
     }
 
-    pub fn default() -> Pool<T> {
+    /// Creates a pool that validates every candidate with `is_usable` before handing it out,
+    /// discarding dead entries instead of returning them (see [`Checkout`]).
+    pub fn new_checked<F>(max_pool_indexes: usize, max_single_pool_size: usize, expiration: Duration, is_usable: F) -> Pool<T, K>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        Pool {
+            max_pool_indexes,
+            max_single_pool_size,
+            expiration,
+            objects: RwLock::new(BTreeMap::new()),
+            recycler: None,
+            checker: Some(Arc::new(is_usable)),
+            generations: RwLock::new(BTreeMap::new()),
+            #[cfg(feature = "async")]
+            semaphores: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Creates a pool that resets every object through its own [`Recycle`] implementation,
+    /// rather than a separate closure. Use [`Pool::new_with_recycler`] instead for types you
+    /// don't own, where implementing `Recycle` isn't an option.
+    pub fn new_recycling(max_pool_indexes: usize, max_single_pool_size: usize, expiration: Duration) -> Pool<T, K>
+    where
+        T: Recycle + 'static,
+    {
+        Self::new_with_recycler(max_pool_indexes, max_single_pool_size, expiration, <T as Recycle>::reset)
+    }
+
+    /// Creates a pool that validates every candidate through its own [`Checkout`] implementation,
+    /// rather than a separate closure. Use [`Pool::new_checked`] instead for types you don't own,
+    /// where implementing `Checkout` isn't an option.
+    pub fn new_checking(max_pool_indexes: usize, max_single_pool_size: usize, expiration: Duration) -> Pool<T, K>
+    where
+        T: Checkout + 'static,
+    {
+        Self::new_checked(max_pool_indexes, max_single_pool_size, expiration, <T as Checkout>::is_usable)
+    }
+
+    pub fn default() -> Pool<T, K> {
         Pool::new(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION)
     }
 
@@ -138,7 +409,11 @@ impl<T> Pool<T> {
         self.objects.read().len()
     }
 
-    pub fn len(&self, item: &str) -> usize {
+    pub fn len<Q>(&self, item: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         match self.objects.read().get(item) {
             Some(item) => item.len(),
             None => 0,
@@ -149,62 +424,122 @@ impl<T> Pool<T> {
         self.size() >= self.max_pool_indexes
     }
 
+    /// Finds the index whose oldest still-pooled object was created furthest in the past.
+    fn oldest_index(&self) -> Option<K> {
+        self.objects
+            .read()
+            .iter()
+            .filter_map(|(key, objects)| objects.iter().map(|object| object.start_time).min().map(|start_time| (key.clone(), start_time)))
+            .min_by_key(|(_, start_time)| *start_time)
+            .map(|(key, _)| key)
+    }
+
     fn expunge_oldest(&self) {
         if !self.is_full() {
             debug!("Object pool is not full, nothing to remove");
             return;
         }
-        let mut last = String::new();
-        if let Some((obj, _)) = self.objects.read().iter().next() {
-            last = obj.clone();
+        match self.oldest_index() {
+            Some(key) => {
+                debug!("Removing oldest element in the queue: {:?}", key);
+                self.objects.write().remove(&key);
+                self.bump_generation(&key);
+                // Safe to drop: outstanding permits hold their own `Arc<Semaphore>` clone, so
+                // removing the map entry can't invalidate one already acquired. A reused key
+                // just gets a fresh semaphore with full capacity next time.
+                #[cfg(feature = "async")]
+                self.semaphores.write().remove(&key);
+            }
+            None => {
+                error!("Unable to find an element to remove from the queue, next allocation could fail");
+            }
         }
-        if !last.is_empty() {
-            debug!("Removing oldest element in the queue: {}", last);
-            self.objects.write().remove(&last);
-        } else {
-            error!("Unable to find an element to remove from the queue, next allocation could fail");
+    }
+
+    /// Returns the generation counter for `item`, creating one starting at `0` on first use.
+    fn generation_for<Q>(&self, item: &Q) -> Arc<AtomicU64>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(generation) = self.generations.read().get(item) {
+            return Arc::clone(generation);
         }
+        Arc::clone(self.generations.write().entry(item.to_owned()).or_insert_with(|| Arc::new(AtomicU64::new(0))))
     }
 
-    fn try_pull(&self, item: &str) -> Option<Reusable<T>> {
-        match self.objects.write().get_mut(item) {
-            Some(objects) => {
-                info!("Pool for {} is currently of {} objects", item, objects.len());
-                if objects.len() > self.max_single_pool_size {
-                    objects.pop();
-                }
-                match objects.pop() {
-                    Some(object) => {
-                        if object.expired(self.expiration) {
-                            info!(
-                                "Element {} has reached expiration time of {} ms, evicting from pool",
-                                item,
-                                object.start_time.elapsed().as_millis()
-                            );
-                            None
-                        } else {
-                            info!(
-                                "Reusing element pool {} created {} ms ago",
-                                item,
-                                object.start_time.elapsed().as_millis()
-                            );
-                            Some(Reusable::new(self, item.to_string(), object.start_time, object.inner))
-                        }
-                    }
-                    None => {
-                        debug!("Element {} pool is empty", item);
-                        None
-                    }
-                }
-            }
+    /// Bumps `item`'s generation counter, invalidating any [`DetachedHandle`] pulled before this
+    /// index was emptied out or otherwise replaced.
+    fn bump_generation<Q>(&self, item: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        self.generation_for(item).fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Pops the next usable candidate for `item`, draining expired and dead entries along the
+    /// way. Shared by [`Pool::try_pull`] and [`Pool::try_pull_owned`].
+    fn pop_usable<Q>(&self, item: &Q) -> Option<(Instant, T)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ?Sized,
+    {
+        let mut objects = self.objects.write();
+        let objects = match objects.get_mut(item) {
+            Some(objects) => objects,
             None => {
-                debug!("Unable to find element {} in objects pool", item);
-                None
+                debug!("Unable to find element {:?} in objects pool", item);
+                return None;
             }
+        };
+        info!("Pool for {:?} is currently of {} objects", item, objects.len());
+        if objects.len() > self.max_single_pool_size {
+            objects.pop();
         }
+        while let Some(object) = objects.pop() {
+            if object.expired(self.expiration) {
+                info!(
+                    "Element {:?} has reached expiration time of {} ms, evicting from pool",
+                    item,
+                    object.start_time.elapsed().as_millis()
+                );
+                continue;
+            }
+            if let Some(checker) = &self.checker {
+                if !checker(&object.inner) {
+                    info!("Element {:?} failed liveness check, discarding and trying the next candidate", item);
+                    continue;
+                }
+            }
+            info!("Reusing element pool {:?} created {} ms ago", item, object.start_time.elapsed().as_millis());
+            return Some((object.start_time, object.inner));
+        }
+        debug!("Element {:?} pool is empty", item);
+        None
+    }
+
+    fn try_pull<Q>(&self, item: &Q) -> Option<Reusable<'_, T, K>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ToOwned<Owned = K> + ?Sized,
+    {
+        self.pop_usable(item).map(|(start_time, inner)| Reusable::new(self, item.to_owned(), start_time, inner))
+    }
+
+    fn try_pull_owned<Q>(self: &Arc<Self>, item: &Q) -> Option<OwnedReusable<T, K>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ToOwned<Owned = K> + ?Sized,
+    {
+        self.pop_usable(item).map(|(start_time, inner)| OwnedReusable::new(Arc::clone(self), item.to_owned(), start_time, inner))
     }
 
-    fn attach_time(&self, item: &str, start_time: Instant, t: T) {
+    fn attach_time<Q>(&self, item: &Q, start_time: Instant, t: T)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
         self.expunge_oldest();
         if self.objects.read().contains_key(item) {
             debug!("Creating new pool of {} elements and attatching object to it", self.max_single_pool_size);
@@ -215,43 +550,110 @@ impl<T> Pool<T> {
                 self.len(item) + 1,
                 self.max_single_pool_size
             );
-            self.objects.write().insert(item.to_string(), vec![Inner::new(t, start_time)]);
+            self.objects.write().insert(item.to_owned(), vec![Inner::new(t, start_time)]);
+            self.bump_generation(item);
         }
     }
 
-    pub fn pull<F: Fn() -> T>(&self, item: &str, fallback: F) -> Reusable<T> {
+    pub fn pull<F: Fn() -> T, Q>(&self, item: &Q, fallback: F) -> Reusable<'_, T, K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ToOwned<Owned = K> + ?Sized,
+    {
         match self.try_pull(item) {
             Some(object) => object,
             None => {
-                info!("Creating new element {} with a pool of {} instances", item, self.max_single_pool_size);
-                for _ in 0..self.max_single_pool_size {
-                    self.attach(item, fallback())
+                // Only attach `max_single_pool_size - 1` batch-manufactured objects, then wrap
+                // the last fallback object directly instead of attaching and recursing through
+                // try_pull: a `Checkout` checker is only meant to catch objects that died after
+                // being pooled, not to second-guess a value the caller just handed us. Recursing
+                // through try_pull here would apply the checker to this freshly made batch too,
+                // and a checker that (validly) rejects every fresh object would recurse forever,
+                // manufacturing a new batch each time until the stack overflows.
+                info!("Creating new element {:?} with a pool of {} instances", item, self.max_single_pool_size);
+                for _ in 0..self.max_single_pool_size.saturating_sub(1) {
+                    self.attach(item, fallback());
                 }
-                self.pull(item, fallback)
+                Reusable::new(self, item.to_owned(), Instant::now(), fallback())
             }
         }
     }
 
-    pub fn attach(&self, item: &str, t: T) {
+    pub fn attach<Q>(&self, item: &Q, t: T)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
         self.attach_time(item, Instant::now(), t);
     }
+
+    /// Re-pools a [`DetachedHandle`] obtained from [`Reusable::detach`], refusing to do so if its
+    /// index has since moved on to a new generation (emptied out by the reaper, expunged under
+    /// memory pressure, ...). Returns `Err(T)` with the original value on a stale generation so
+    /// the caller can decide what to do with it instead of silently resurrecting a dead index.
+    pub fn reattach(&self, handle: DetachedHandle<T, K>) -> Result<(), T> {
+        if self.generation_for(&handle.item).load(Ordering::SeqCst) != handle.generation {
+            info!("Refusing to reattach stale element {:?}, its index has moved on to a new generation", handle.item);
+            return Err(handle.value);
+        }
+        self.attach(&handle.item, handle.value);
+        Ok(())
+    }
+
+    /// Like [`Pool::pull`], but returns an [`OwnedReusable`] that owns its share of the pool via
+    /// `Arc`, so the lease can be moved into a spawned thread or task instead of being tied to
+    /// the stack frame that borrowed `&Pool<T, K>`.
+    pub fn pull_owned<F: Fn() -> T, Q>(self: &Arc<Self>, item: &Q, fallback: F) -> OwnedReusable<T, K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.try_pull_owned(item) {
+            Some(object) => object,
+            None => {
+                // See the identical comment in `Pool::pull`: only attach `max_single_pool_size -
+                // 1` objects and wrap the last one directly, so a `Checkout` checker rejecting
+                // every freshly manufactured object can't recurse forever.
+                info!("Creating new element {:?} with a pool of {} instances", item, self.max_single_pool_size);
+                for _ in 0..self.max_single_pool_size.saturating_sub(1) {
+                    self.attach(item, fallback());
+                }
+                OwnedReusable::new(Arc::clone(self), item.to_owned(), Instant::now(), fallback())
+            }
+        }
+    }
 }
 
-pub struct Reusable<'a, T> {
-    item: String,
-    pool: &'a Pool<T>,
+pub struct Reusable<'a, T, K = String>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    item: K,
+    pool: &'a Pool<T, K>,
     data: ManuallyDrop<T>,
     start_time: Instant,
+    generation: u64,
 }
 
-impl<'a, T> Reusable<'a, T> {
-    pub fn new(pool: &'a Pool<T>, item: String, start_time: Instant, t: T) -> Self {
-        Self { item, pool, data: ManuallyDrop::new(t), start_time }
+impl<'a, T, K> Reusable<'a, T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    pub fn new(pool: &'a Pool<T, K>, item: K, start_time: Instant, t: T) -> Self {
+        let generation = pool.generation_for(&item).load(Ordering::SeqCst);
+        Self { item, pool, data: ManuallyDrop::new(t), start_time, generation }
     }
 
-    pub fn detach(mut self) -> (&'a Pool<T>, T) {
-        let ret = unsafe { (self.pool, self.take()) };
-        info!("Detaching object from element {} pool", self.item);
+    /// Detaches the object from this lease, returning the pool it came from and a
+    /// [`DetachedHandle`] tagged with the generation the object was pulled under. Pass the handle
+    /// to [`Pool::reattach`] to re-pool it; if the index was emptied out or expunged while this
+    /// lease was held, the generation will have moved on and `reattach` refuses to resurrect it.
+    pub fn detach(mut self) -> (&'a Pool<T, K>, DetachedHandle<T, K>) {
+        let generation = self.generation;
+        let item = self.item.clone();
+        let value = unsafe { self.take() };
+        let ret = (self.pool, DetachedHandle { item, generation, value });
+        info!("Detaching object from element {:?} pool", self.item);
         forget(self);
         ret
     }
@@ -261,7 +663,10 @@ impl<'a, T> Reusable<'a, T> {
     }
 }
 
-impl<'a, T> Deref for Reusable<'a, T> {
+impl<'a, T, K> Deref for Reusable<'a, T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -269,20 +674,286 @@ impl<'a, T> Deref for Reusable<'a, T> {
     }
 }
 
-impl<'a, T> DerefMut for Reusable<'a, T> {
+impl<'a, T, K> DerefMut for Reusable<'a, T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.data
     }
 }
 
-impl<'a, T> Drop for Reusable<'a, T> {
+impl<'a, T, K> Drop for Reusable<'a, T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
     fn drop(&mut self) {
-        let value = unsafe { self.take() };
-        info!("Re-attatching object to element {} object pool", self.item);
+        let mut value = unsafe { self.take() };
+        if let Some(recycler) = &self.pool.recycler {
+            recycler(&mut value);
+        }
+        info!("Re-attatching object to element {:?} object pool", self.item);
+        self.pool.attach_time(&self.item, self.start_time, value)
+    }
+}
+
+/// A value detached from a [`Pool`] via [`Reusable::detach`], tagged with the generation its
+/// index was on at the time. [`Pool::reattach`] compares that generation against the index's
+/// current one before re-pooling it, so a handle that outlives its index's eviction can't
+/// silently resurrect it.
+pub struct DetachedHandle<T, K = String> {
+    item: K,
+    generation: u64,
+    value: T,
+}
+
+impl<T, K> DetachedHandle<T, K> {
+    /// Discards the generation tracking and returns the bare value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, K> Deref for DetachedHandle<T, K> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, K> DerefMut for DetachedHandle<T, K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+/// A lease pulled from a [`Pool`] that owns its share of the pool via `Arc<Pool<T, K>>` instead
+/// of borrowing it, so it can be moved into a spawned thread or task. Re-attaches the object to
+/// the pool on drop, exactly like [`Reusable`].
+pub struct OwnedReusable<T, K = String>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    item: K,
+    pool: Arc<Pool<T, K>>,
+    data: ManuallyDrop<T>,
+    start_time: Instant,
+}
+
+impl<T, K> OwnedReusable<T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    pub fn new(pool: Arc<Pool<T, K>>, item: K, start_time: Instant, t: T) -> Self {
+        Self { item, pool, data: ManuallyDrop::new(t), start_time }
+    }
+
+    unsafe fn take(&mut self) -> T {
+        ManuallyDrop::take(&mut self.data)
+    }
+}
+
+impl<T, K> Deref for OwnedReusable<T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T, K> DerefMut for OwnedReusable<T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T, K> Drop for OwnedReusable<T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    fn drop(&mut self) {
+        let mut value = unsafe { self.take() };
+        if let Some(recycler) = &self.pool.recycler {
+            recycler(&mut value);
+        }
+        info!("Re-attatching object to element {:?} object pool", self.item);
         self.pool.attach_time(&self.item, self.start_time, value)
     }
 }
 
+#[cfg(feature = "async")]
+impl<T, K> Pool<T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    /// Returns the semaphore gating `item`'s subpool, creating one with `max_single_pool_size`
+    /// permits on first use.
+    fn semaphore<Q>(&self, item: &Q) -> Arc<tokio::sync::Semaphore>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(semaphore) = self.semaphores.read().get(item) {
+            return Arc::clone(semaphore);
+        }
+        Arc::clone(
+            self.semaphores
+                .write()
+                .entry(item.to_owned())
+                .or_insert_with(|| Arc::new(tokio::sync::Semaphore::new(self.max_single_pool_size))),
+        )
+    }
+
+    /// Pulls an object from the pool, awaiting a free slot instead of eagerly over-allocating
+    /// when `item`'s subpool is already at `max_single_pool_size`.
+    ///
+    /// Unlike [`Pool::pull`], which manufactures a whole batch of fresh objects the first time an
+    /// index is seen, this only ever runs `fallback` once per call, and blocks once the index is
+    /// at capacity until an [`AsyncReusable`] is dropped and returns its permit.
+    pub async fn pull_async<F: Fn() -> T, Q>(&self, item: &Q, fallback: F) -> AsyncReusable<'_, T, K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ToOwned<Owned = K> + ?Sized,
+    {
+        let permit = self.semaphore(item).acquire_owned().await.expect("index semaphore is never closed");
+        let inner = match self.try_pull(item) {
+            Some(object) => object,
+            None => {
+                // Wrap the freshly created object directly instead of attaching it and pulling
+                // it back out: the pool takes its write lock separately for each step, so a
+                // concurrent `pull_async`/reaper sweep could steal or evict it between the two,
+                // and a liveness checker has no business rejecting an object nothing has used
+                // yet. It gets attached for real when this lease is dropped, same as `pull`'s
+                // freshly-created objects.
+                info!("Index {:?} below capacity, creating a single new element", item);
+                Reusable::new(self, item.to_owned(), Instant::now(), fallback())
+            }
+        };
+        AsyncReusable { inner, _permit: permit }
+    }
+
+    /// Returns a stream that yields a lease for `item` every time one frees up, mirroring the
+    /// `lease` crate's `PoolStream`.
+    pub fn stream<'a, F: Fn() -> T + 'a, Q>(
+        &'a self,
+        item: &'a Q,
+        fallback: F,
+    ) -> impl futures_core::Stream<Item = AsyncReusable<'a, T, K>> + 'a
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ToOwned<Owned = K> + ?Sized,
+    {
+        async_stream::stream! {
+            loop {
+                yield self.pull_async(item, &fallback).await;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, K> Pool<T, K>
+where
+    T: Send + Sync + 'static,
+    K: Ord + Clone + fmt::Debug + Send + Sync + 'static,
+{
+    /// Removes expired objects from every index, and drops now-empty indexes altogether.
+    fn reap_expired(&self) {
+        let mut emptied = Vec::new();
+        self.objects.write().retain(|item, objects| {
+            objects.retain(|object| {
+                let expired = object.expired(self.expiration);
+                if expired {
+                    info!("Reaper evicting expired element from index {:?}", item);
+                }
+                !expired
+            });
+            let keep = !objects.is_empty();
+            if !keep {
+                emptied.push(item.clone());
+            }
+            keep
+        });
+        for item in emptied {
+            self.bump_generation(&item);
+            // See the identical comment in `expunge_oldest`: safe to drop since outstanding
+            // permits hold their own `Arc<Semaphore>` clone.
+            self.semaphores.write().remove(&item);
+        }
+    }
+
+    /// Spawns a background task that periodically sweeps every index for expired objects,
+    /// mirroring hyper's idle-connection reaper. Without this, expiration is only enforced lazily
+    /// inside `try_pull`, so a burst of activity followed by silence leaves dead objects (and
+    /// their OS resources) pooled indefinitely. Dropping the returned [`ReaperHandle`] stops the
+    /// task.
+    pub fn spawn_reaper(self: &Arc<Self>, interval: Duration) -> ReaperHandle {
+        let pool = Arc::clone(self);
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                pool.reap_expired();
+            }
+        });
+        ReaperHandle { task }
+    }
+}
+
+/// Stops the background task spawned by [`Pool::spawn_reaper`] when dropped.
+#[cfg(feature = "async")]
+pub struct ReaperHandle {
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "async")]
+impl Drop for ReaperHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A lease pulled via [`Pool::pull_async`]. Holds the permit that caps its index's subpool at
+/// `max_single_pool_size` concurrent leases; the permit, and the underlying object, are released
+/// back to the pool when this is dropped.
+#[cfg(feature = "async")]
+pub struct AsyncReusable<'a, T, K = String>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    inner: Reusable<'a, T, K>,
+    _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+#[cfg(feature = "async")]
+impl<'a, T, K> Deref for AsyncReusable<'a, T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T, K> DerefMut for AsyncReusable<'a, T, K>
+where
+    K: Ord + Clone + fmt::Debug,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,48 +976,76 @@ mod tests {
     #[test]
     fn test_detach() {
         let pool = Pool::default();
-        let (pool, object) = pool.pull("item1", || Obj::new(1)).detach();
+        let (pool, object) = pool.pull(&"item1".to_string(), || Obj::new(1)).detach();
         assert_eq!(object.idx, 1);
-        assert_eq!(pool.len("item1"), 7);
+        assert_eq!(pool.len(&"item1".to_string()), 7);
         drop(object);
-        assert_eq!(pool.len("item1"), 7);
+        assert_eq!(pool.len(&"item1".to_string()), 7);
     }
 
     #[test]
     fn test_detach_then_attach() {
         let pool = Pool::default();
-        let (pool, object) = pool.pull("item1", || Obj::new(1)).detach();
+        let (pool, object) = pool.pull(&"item1".to_string(), || Obj::new(1)).detach();
         assert_eq!(object.idx, 1);
-        assert_eq!(pool.len("item1"), 7);
-        pool.attach("item1", object);
-        assert_eq!(pool.try_pull("item1").unwrap().idx, 1);
-        assert_eq!(pool.len("item1"), 8);
+        assert_eq!(pool.len(&"item1".to_string()), 7);
+        pool.reattach(object).unwrap();
+        assert_eq!(pool.try_pull(&"item1".to_string()).unwrap().idx, 1);
+        assert_eq!(pool.len(&"item1".to_string()), 8);
+    }
+
+    #[test]
+    fn test_reattach_rejects_stale_generation() {
+        let pool: Pool<Obj> = Pool::new(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION);
+        let (pool, handle) = pool.pull(&"item1".to_string(), || Obj::new(1)).detach();
+        pool.objects.write().remove(&"item1".to_string());
+        pool.bump_generation(&"item1".to_string());
+        match pool.reattach(handle) {
+            Err(object) => assert_eq!(object.idx, 1),
+            Ok(()) => panic!("expected reattach to refuse a stale generation"),
+        }
+        assert_eq!(pool.len(&"item1".to_string()), 0);
+    }
+
+    #[test]
+    fn test_detach_captures_generation_at_pull_time_not_detach_time() {
+        let pool: Pool<Obj> = Pool::new(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION);
+        let object = pool.pull(&"item1".to_string(), || Obj::new(1));
+        // Expunge the index while the lease is still held, so the generation moves on before
+        // `detach` is ever called.
+        pool.objects.write().remove(&"item1".to_string());
+        pool.bump_generation(&"item1".to_string());
+        let (pool, handle) = object.detach();
+        match pool.reattach(handle) {
+            Err(object) => assert_eq!(object.idx, 1),
+            Ok(()) => panic!("expected reattach to refuse an object pulled under a stale generation"),
+        }
     }
 
     #[test]
     fn test_pull_and_size() {
         let pool = Pool::default();
-        pool.attach("item1", Obj::new(1));
+        pool.attach(&"item1".to_string(), Obj::new(1));
         assert_eq!(pool.size(), 1);
 
-        let object1 = pool.try_pull("item1");
-        let object2 = pool.try_pull("item1");
-        let object3 = pool.pull("item2", || Obj::new(2));
+        let object1 = pool.try_pull(&"item1".to_string());
+        let object2 = pool.try_pull(&"item1".to_string());
+        let object3 = pool.pull(&"item2".to_string(), || Obj::new(2));
         assert_eq!(pool.size(), 2);
 
         assert_eq!(object1.is_some(), true);
         assert_eq!(object2.is_none(), true);
 
-        assert_eq!(pool.len("item1"), 0);
+        assert_eq!(pool.len(&"item1".to_string()), 0);
         drop(object1);
-        assert_eq!(pool.len("item1"), 1);
+        assert_eq!(pool.len(&"item1".to_string()), 1);
         drop(object2);
-        assert_eq!(pool.len("item1"), 1);
+        assert_eq!(pool.len(&"item1".to_string()), 1);
 
         assert_eq!(object3.idx, 2);
-        assert_eq!(pool.len("item2"), 7);
+        assert_eq!(pool.len(&"item2".to_string()), 7);
         drop(object3);
-        assert_eq!(pool.len("item2"), 8);
+        assert_eq!(pool.len(&"item2".to_string()), 8);
     }
 
     #[test]
@@ -380,6 +1079,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_recycle_on_return() {
+        let pool: Pool<Vec<u8>> = Pool::new_with_recycler(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION, Vec::clear);
+        let mut object = pool.pull(&"item1".to_string(), Vec::new);
+        object.push(1);
+        object.push(2);
+        assert_eq!(object.len(), 2);
+        drop(object);
+        let object = pool.try_pull(&"item1".to_string()).unwrap();
+        assert_eq!(object.len(), 0);
+    }
+
+    #[derive(Debug)]
+    struct RecyclingObj {
+        dirty: bool,
+    }
+
+    impl Recycle for RecyclingObj {
+        fn reset(&mut self) {
+            self.dirty = false;
+        }
+    }
+
+    #[test]
+    fn test_new_recycling_calls_recycle_reset_automatically() {
+        let pool: Pool<RecyclingObj> = Pool::new_recycling(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION);
+        let object = pool.pull(&"item1".to_string(), || RecyclingObj { dirty: true });
+        assert_eq!(object.dirty, true);
+        drop(object);
+        let object = pool.try_pull(&"item1".to_string()).unwrap();
+        assert_eq!(object.dirty, false);
+    }
+
+    #[test]
+    fn test_checked_pool_skips_dead_candidates() {
+        let pool: Pool<Obj> = Pool::new_checked(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION, |obj: &Obj| obj.idx != 0);
+        pool.attach(&"item1".to_string(), Obj::new(0));
+        pool.attach(&"item1".to_string(), Obj::new(1));
+        let object = pool.try_pull(&"item1".to_string()).unwrap();
+        assert_eq!(object.idx, 1);
+        assert_eq!(pool.try_pull(&"item1".to_string()).is_none(), true);
+    }
+
+    #[derive(Debug)]
+    struct CheckedObj {
+        alive: bool,
+    }
+
+    impl Checkout for CheckedObj {
+        fn is_usable(&self) -> bool {
+            self.alive
+        }
+    }
+
+    #[test]
+    fn test_new_checking_consults_checkout_is_usable_automatically() {
+        let pool: Pool<CheckedObj> = Pool::new_checking(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION);
+        pool.attach(&"item1".to_string(), CheckedObj { alive: false });
+        pool.attach(&"item1".to_string(), CheckedObj { alive: true });
+        let object = pool.try_pull(&"item1".to_string()).unwrap();
+        assert_eq!(object.alive, true);
+        assert_eq!(pool.try_pull(&"item1".to_string()).is_none(), true);
+    }
+
+    #[test]
+    fn test_pull_does_not_recurse_when_checker_rejects_every_fallback_object() {
+        let pool: Pool<Obj> = Pool::new_checked(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION, |_: &Obj| false);
+        let object = pool.pull(&"item1".to_string(), || Obj::new(1));
+        assert_eq!(object.idx, 1);
+    }
+
+    #[test]
+    fn test_pull_owned_across_threads() {
+        let pool = Arc::new(Pool::default());
+        let object = pool.pull_owned(&"item1".to_string(), || Obj::new(1));
+        assert_eq!(object.idx, 1);
+        let handle = thread::spawn(move || {
+            assert_eq!(object.idx, 1);
+        });
+        handle.join().unwrap();
+        assert_eq!(pool.len(&"item1".to_string()), 8);
+    }
+
+    #[test]
+    fn test_custom_key_type() {
+        let pool: Pool<String, u16> = Pool::default();
+        let object = pool.pull(&22, || String::from("ssh-connection"));
+        assert_eq!(*object, "ssh-connection");
+        assert_eq!(pool.len(&22), 7);
+    }
+
+    #[test]
+    fn test_str_literal_looks_up_a_string_keyed_pool() {
+        let pool: Pool<Obj> = Pool::default();
+        let object = pool.pull("item1", || Obj::new(1));
+        assert_eq!(object.idx, 1);
+        drop(object);
+        assert_eq!(pool.len("item1"), DEFAULT_SINGLE_POOL_SIZE);
+    }
+
+    #[test]
+    fn test_expunge_oldest_by_age_not_lexicographic_order() {
+        let pool: Pool<Obj> = Pool::new(2, DEFAULT_SINGLE_POOL_SIZE, DEFAULT_EXPIRATION);
+        pool.attach(&"zzz".to_string(), Obj::new(1));
+        thread::sleep(Duration::from_millis(10));
+        pool.attach(&"aaa".to_string(), Obj::new(2));
+        assert_eq!(pool.size(), 2);
+        pool.attach(&"mmm".to_string(), Obj::new(3));
+        assert_eq!(pool.size(), 2);
+        assert_eq!(pool.len(&"zzz".to_string()), 0);
+        assert_eq!(pool.len(&"aaa".to_string()), 1);
+        assert_eq!(pool.len(&"mmm".to_string()), 1);
+    }
+
     #[test]
     fn test_smoke() {
         let pool = Pool::default();
@@ -392,3 +1205,48 @@ mod tests {
         }
     }
 }
+
+#[cfg(all(test, feature = "async"))]
+mod async_tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug)]
+    struct Obj;
+
+    #[tokio::test]
+    async fn test_pull_async_blocks_at_capacity_and_unblocks_on_drop() {
+        let pool: Pool<Obj> = Pool::new(DEFAULT_POOL_INDEXES, 1, DEFAULT_EXPIRATION);
+        let first = pool.pull_async(&"item1".to_string(), || Obj).await;
+        let blocked = tokio::time::timeout(Duration::from_millis(50), pool.pull_async(&"item1".to_string(), || Obj)).await;
+        assert!(blocked.is_err(), "pull_async should block once the index is at max_single_pool_size");
+        drop(first);
+        let unblocked = tokio::time::timeout(Duration::from_millis(50), pool.pull_async(&"item1".to_string(), || Obj)).await;
+        assert!(unblocked.is_ok(), "pull_async should unblock once a lease is dropped");
+    }
+
+    #[tokio::test]
+    async fn test_reap_expired_evicts_expired_entries_and_drops_empty_indexes() {
+        let pool: Pool<Obj> = Pool::new(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, Duration::from_millis(20));
+        pool.attach(&"item1".to_string(), Obj);
+        assert_eq!(pool.len(&"item1".to_string()), 1);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        pool.reap_expired();
+        assert_eq!(pool.len(&"item1".to_string()), 0);
+        assert_eq!(pool.size(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reaper_handle_drop_stops_the_background_sweep() {
+        let pool = Arc::new(Pool::new(DEFAULT_POOL_INDEXES, DEFAULT_SINGLE_POOL_SIZE, Duration::from_millis(20)));
+        let handle = pool.spawn_reaper(Duration::from_millis(5));
+        drop(handle);
+        pool.attach(&"item1".to_string(), Obj);
+        // If dropping the handle hadn't aborted the sweep task, a reaper tick in this window
+        // would have evicted the now-expired object. `len` doesn't prune lazily like `try_pull`
+        // does, so it only reflects the reaper's own work.
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(pool.len(&"item1".to_string()), 1);
+    }
+}